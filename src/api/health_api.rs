@@ -0,0 +1,12 @@
+use actix_web::{get, web::Data, HttpResponse};
+use serde_json::json;
+
+use crate::repository::surrealdb_repo::SurrealDBRepo;
+
+#[get("/health")]
+pub async fn health_check(db: Data<SurrealDBRepo>) -> HttpResponse {
+    match db.ds.execute("RETURN 1", &db.ses, None, false).await {
+        Ok(_) => HttpResponse::Ok().json(json!({ "status": "ok", "db": "up" })),
+        Err(err) => HttpResponse::ServiceUnavailable().json(json!({ "status": "error", "db": err.to_string() })),
+    }
+}
@@ -0,0 +1,2 @@
+pub mod health_api;
+pub mod todo_api;
@@ -1,52 +1,88 @@
+use std::sync::Arc;
+
+use actix::Addr;
 use actix_web::{
     post, get, put, delete,
-    web::{Data, Json, Path},
+    web::{Bytes, Data, Json, Path, Query},
     HttpResponse,
 };
+use serde::Deserialize;
+use surrealdb::sql::Value;
+use surrealdb::{Datastore, Session};
+use tokio::sync::broadcast::error::RecvError;
+use validator::Validate;
+
+use crate::prelude::*;
+use crate::utils::macros::map;
+use crate::repository::db_actor::{CreateTodo, DbActor, DeleteTodo, GetTodo, ListTodos, UpdateTodo};
+use crate::repository::surrealdb_repo::SurrealDBRepo;
+use crate::model::todo_model::{Todo, TodoPatch};
+
+fn default_page_size() -> i64 {
+    20
+}
 
-use crate::repository::{surrealdb_repo::SurrealDBRepo};
-use crate::model::todo_model::{Todo, TodoBMC, TodoPatch};
+#[derive(Debug, Deserialize)]
+pub struct PaginationParams {
+    #[serde(default)]
+    pub page: i64,
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+fn error_response(err: Error) -> HttpResponse {
+    match err {
+        Error::Validation(errors) => HttpResponse::UnprocessableEntity().json(errors.field_errors()),
+        err => HttpResponse::InternalServerError().body(err.to_string()),
+    }
+}
 
 #[post("/todos")]
-pub async fn create_todo(db: Data<SurrealDBRepo>, new_todo: Json<Todo>) -> HttpResponse {
+pub async fn create_todo(db: Data<Addr<DbActor>>, new_todo: Json<Todo>) -> HttpResponse {
     let data = Todo {
         id: None,
         title: new_todo.title.to_owned(),
         body: new_todo.body.to_owned(),
     };
-    
-    let todo_detail = TodoBMC::create(db, "todo", data ).await;
+
+    if let Err(errors) = data.validate() {
+        return error_response(Error::Validation(errors));
+    }
+
+    let todo_detail = db.send(CreateTodo(data)).await;
 
     match todo_detail {
-         Ok(todo) => HttpResponse::Ok().json(todo),
-         Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+         Ok(Ok(todo)) => HttpResponse::Ok().json(todo),
+         Ok(Err(err)) => error_response(err),
+         Err(err) => error_response(err.into()),
     }
 }
 
 #[get("/todos/{id}")]
-pub async fn get_todo(db: Data<SurrealDBRepo>, path: Path<String>) -> HttpResponse {
+pub async fn get_todo(db: Data<Addr<DbActor>>, path: Path<String>) -> HttpResponse {
     let id = path.into_inner();
-    
+
     if id.is_empty() {
         return HttpResponse::BadRequest().body("invalid ID");
     }
-    
-    let todo_detail = TodoBMC::get(db, &id).await;
-    
+
+    let todo_detail = db.send(GetTodo(id)).await;
+
     match todo_detail {
-        Ok(todo) => HttpResponse::Ok().json(todo),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(Ok(todo)) => HttpResponse::Ok().json(todo),
+        Ok(Err(err)) => error_response(err),
+        Err(err) => error_response(err.into()),
     }
 }
 
 #[put("/todos/{id}")]
 pub async fn update_todo(
-    db: Data<SurrealDBRepo>,
+    db: Data<Addr<DbActor>>,
     path: Path<String>,
     todo_patch: Json<TodoPatch>,
 ) -> HttpResponse {
     let id = path.into_inner();
-    
+
     if id.is_empty() {
         return HttpResponse::BadRequest().body("invalid ID");
     };
@@ -55,36 +91,117 @@ pub async fn update_todo(
         title: todo_patch.title.to_owned(),
         body: todo_patch.body.to_owned(),
     };
-    
-    let update_result = TodoBMC::update(db, &id, data).await;
-    
+
+    if let Err(errors) = data.validate() {
+        return error_response(Error::Validation(errors));
+    }
+
+    let update_result = db.send(UpdateTodo(id, data)).await;
+
     match update_result {
-        Ok(todo) => HttpResponse::Ok().json(todo),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(Ok(todo)) => HttpResponse::Ok().json(todo),
+        Ok(Err(err)) => error_response(err),
+        Err(err) => error_response(err.into()),
     }
 }
 #[delete("/todos/{id}")]
-pub async fn delete_todo(db: Data<SurrealDBRepo>, path: Path<String>) -> HttpResponse {
+pub async fn delete_todo(db: Data<Addr<DbActor>>, path: Path<String>) -> HttpResponse {
     let id = path.into_inner();
-    
+
     if id.is_empty() {
         return HttpResponse::BadRequest().body("invalid ID");
     };
-    
-    let result = TodoBMC::delete(db, &id).await;
-    
+
+    let result = db.send(DeleteTodo(id)).await;
+
     match result {
-        Ok(todo) => HttpResponse::Ok().json(todo),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(Ok(todo)) => HttpResponse::Ok().json(todo),
+        Ok(Err(err)) => error_response(err),
+        Err(err) => error_response(err.into()),
     }
 }
 
 #[get("/todos")]
-pub async fn get_todos(db: Data<SurrealDBRepo>) -> HttpResponse {
-    let result = TodoBMC::get_all(db).await;
-    
+pub async fn get_todos(db: Data<Addr<DbActor>>, pagination: Query<PaginationParams>) -> HttpResponse {
+    let PaginationParams { page, page_size } = pagination.into_inner();
+
+    let result = db.send(ListTodos { page, page_size }).await;
+
     match result {
-        Ok(todos) => HttpResponse::Ok().json(todos),
-        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(Ok(page)) => HttpResponse::Ok().json(page),
+        Ok(Err(err)) => error_response(err),
+        Err(err) => error_response(err.into()),
    }
-}
\ No newline at end of file
+}
+
+/// Tears the `LIVE SELECT` back down with `KILL $id` once the SSE stream is
+/// dropped, whether that's a clean end-of-stream or the client disconnecting.
+struct LiveQueryGuard {
+    ds: Arc<Datastore>,
+    ses: Session,
+    id: surrealdb::sql::Uuid,
+}
+
+impl Drop for LiveQueryGuard {
+    fn drop(&mut self) {
+        let ds = self.ds.clone();
+        let ses = self.ses.clone();
+        let id = self.id.clone();
+
+        actix_web::rt::spawn(async move {
+            let vars = map!["id".into() => id.into()];
+            let _ = ds.execute("KILL $id", &ses, Some(vars), false).await;
+        });
+    }
+}
+
+#[get("/todos/live")]
+pub async fn get_todos_live(db: Data<SurrealDBRepo>) -> HttpResponse {
+    let live_res = db.ds.execute("LIVE SELECT * FROM todo", &db.ses, None, true).await;
+
+    let live_id = match live_res {
+        Ok(res) => match res.into_iter().next().map(|r| r.result) {
+            Some(Ok(Value::Uuid(id))) => id,
+            Some(Ok(_)) => return HttpResponse::InternalServerError().body("LIVE SELECT did not return a query id"),
+            Some(Err(err)) => return HttpResponse::InternalServerError().body(err.to_string()),
+            None => return HttpResponse::InternalServerError().body("Did not get a response"),
+        },
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+    };
+
+    let mut notifications = match db.ds.notifications() {
+        Some(notifications) => notifications,
+        None => return HttpResponse::InternalServerError().body("live queries are not enabled on this datastore"),
+    };
+    let ds = db.ds.clone();
+    let ses = db.ses.clone();
+
+    let stream = async_stream::stream! {
+        let _guard = LiveQueryGuard { ds, ses, id: live_id.clone() };
+
+        loop {
+            match notifications.recv().await {
+                Ok(notification) => {
+                    if notification.id != live_id {
+                        continue;
+                    }
+
+                    let payload = serde_json::to_string(&serde_json::json!({
+                        "action": notification.action,
+                        "result": notification.result,
+                    })).unwrap_or_default();
+
+                    yield Ok::<Bytes, actix_web::Error>(Bytes::from(format!("data: {}\n\n", payload)));
+                }
+                // A slow client can fall behind the broadcast channel; skip the
+                // missed notifications instead of killing the whole stream.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .streaming(stream)
+}
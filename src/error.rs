@@ -14,9 +14,18 @@ pub enum Error {
 	#[error("Fail to create. Cause: {0}")]
 	StoreFailToCreate(String),
 
+	#[error("Unsupported SURREAL_PATH scheme: '{0}' (expected 'memory', 'file://...' or 'tikv://...')")]
+	UnsupportedDatastorePath(String),
+
 	#[error(transparent)]
 	Surreal(#[from] surrealdb::Error),
 
 	#[error(transparent)]
 	IO(#[from] std::io::Error),
+
+	#[error(transparent)]
+	Mailbox(#[from] actix::MailboxError),
+
+	#[error(transparent)]
+	Validation(#[from] validator::ValidationErrors),
 }
\ No newline at end of file
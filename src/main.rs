@@ -1,6 +1,7 @@
+use actix::Actor;
 use actix_web::{
     App,
-    HttpServer, 
+    HttpServer,
     web::Data
 };
 
@@ -11,20 +12,29 @@ mod utils;
 mod prelude;
 mod error;
 
+use repository::db_actor::DbActor;
 use repository::surrealdb_repo::SurrealDBRepo;
-use api::todo_api::{create_todo, get_todos, get_todo, update_todo, delete_todo};
+use api::health_api::health_check;
+use api::todo_api::{create_todo, get_todos, get_todo, get_todos_live, update_todo, delete_todo};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let surreal = SurrealDBRepo::init().await.expect("Error connecting to SurrealDB!");
-    
+
     let db_data = Data::new(surreal);
-    
-    HttpServer::new(move || { 
+
+    let db_actor = DbActor::new(db_data.clone()).start();
+
+    let actor_data = Data::new(db_actor);
+
+    HttpServer::new(move || {
         App::new()
             .app_data(db_data.clone())
+            .app_data(actor_data.clone())
+            .service(health_check)
             .service(create_todo)
             .service(get_todos)
+            .service(get_todos_live)
             .service(get_todo)
             .service(update_todo)
             .service(delete_todo)
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use actix_web::web::Data;
+use surrealdb::sql::{Array, Object, Value, thing};
+
+use crate::prelude::*;
+use crate::utils::macros::map;
+use crate::repository::surrealdb_repo::{Creatable, Patchable, SurrealDBRepo};
+
+/// Upper bound on `page_size` so a client can't force `get_all` to dump the
+/// whole table in one request.
+const MAX_PAGE_SIZE: i64 = 100;
+
+/// Shared CRUD implementation for the `todo`/`label`-style entities. A type
+/// opts in by implementing this trait and setting `TABLE`; every method below
+/// then works against `TABLE:<id>` the same way `TodoBMC` used to by hand.
+#[async_trait::async_trait]
+pub trait ModelController: Sized {
+    const TABLE: &'static str;
+
+    async fn create<T: Creatable + Send>(db: Data<SurrealDBRepo>, data: T) -> Result<Object, Error> {
+        let sql = "CREATE type::table($tb) CONTENT $data RETURN *";
+
+        let data: Object = W(data.into()).try_into()?;
+
+        let vars: BTreeMap<String, Value> = map![
+            "tb".into() => Self::TABLE.into(),
+            "data".into() => Value::from(data)];
+
+        let ress = db.ds.execute(sql, &db.ses, Some(vars), false).await?;
+
+        let first_val = ress.into_iter().next().map(|r| r.result).expect("id not returned")?;
+
+        W(first_val.first()).try_into()
+    }
+
+    async fn get(db: Data<SurrealDBRepo>, tid: &str) -> Result<Object, Error> {
+        let sql = "SELECT * FROM $th";
+
+        let tid = format!("{}:{}", Self::TABLE, tid);
+
+        let vars: BTreeMap<String, Value> = map!["th".into() => thing(&tid)?.into()];
+
+        let ress = db.ds.execute(sql, &db.ses, Some(vars), true).await?;
+
+        let first_res = ress.into_iter().next().expect("Did not get a response");
+
+        W(first_res.result?.first()).try_into()
+    }
+
+    async fn get_all(db: Data<SurrealDBRepo>, page: i64, page_size: i64) -> Result<(Vec<Object>, i64), Error> {
+        let page = page.max(0);
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+
+        let ast = format!(
+            "SELECT * FROM {table} LIMIT $limit START $start; SELECT count() FROM {table} GROUP ALL;",
+            table = Self::TABLE
+        );
+
+        let start = page * page_size;
+
+        let vars: BTreeMap<String, Value> = map![
+            "limit".into() => page_size.into(),
+            "start".into() => start.into()];
+
+        let res = db.ds.execute(&ast, &db.ses, Some(vars), true).await?;
+
+        let mut res = res.into_iter();
+
+        let first_res = res.next().expect("Did not get a response");
+
+        let array: Array = W(first_res.result?).try_into()?;
+
+        let items = array.into_iter().map(|value| W(value).try_into()).collect::<Result<Vec<Object>, Error>>()?;
+
+        let total = match res.next() {
+            Some(count_res) => {
+                let count_array: Array = W(count_res.result?).try_into()?;
+
+                match count_array.into_iter().next() {
+                    Some(value) => {
+                        let count_obj: Object = W(value).try_into()?;
+                        count_obj.get("count").map(|v| v.to_owned().as_int()).unwrap_or(0)
+                    }
+                    None => 0,
+                }
+            }
+            None => 0,
+        };
+
+        Ok((items, total))
+    }
+
+    async fn update<T: Patchable + Send>(db: Data<SurrealDBRepo>, tid: &str, data: T) -> Result<Object, Error> {
+        let sql = "UPDATE $th MERGE $data RETURN *";
+
+        let tid = format!("{}:{}", Self::TABLE, tid);
+
+        let vars = map![
+            "th".into() => thing(&tid)?.into(),
+            "data".into() => data.into()];
+
+        let ress = db.ds.execute(sql, &db.ses, Some(vars), true).await?;
+
+        let first_res = ress.into_iter().next().expect("id not returned");
+
+        let result = first_res.result?;
+
+        W(result.first()).try_into()
+    }
+
+    async fn delete(db: Data<SurrealDBRepo>, tid: &str) -> Result<String, Error> {
+        let sql = "DELETE $th RETURN *";
+
+        let tid = format!("{}:{}", Self::TABLE, tid);
+
+        let vars = map!["th".into() => thing(&tid)?.into()];
+
+        let ress = db.ds.execute(sql, &db.ses, Some(vars), false).await?;
+
+        let first_res = ress.into_iter().next().expect("id not returned");
+
+        first_res.result?;
+
+        Ok(tid)
+    }
+}
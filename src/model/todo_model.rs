@@ -1,16 +1,20 @@
 use actix_web::web::Data;
 use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
-use surrealdb::sql::{Object, Value, thing, Array};
+use surrealdb::sql::{Object, Value};
+use validator::Validate;
 
 use crate::prelude::*;
 use crate::utils::{macros::map};
+use crate::model::base::ModelController;
 use crate::repository::surrealdb_repo::{Creatable, Patchable, SurrealDBRepo};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct Todo {
     pub id: Option<String>,
+    #[validate(length(min = 1, message = "title must not be empty"))]
     pub title: String,
+    #[validate(length(max = 5000, message = "body must be 5000 characters or fewer"))]
     pub body: String,
 }
 
@@ -36,9 +40,11 @@ impl From<Todo> for Value {
 
 impl Creatable for Todo{}
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct TodoPatch {
+    #[validate(length(min = 1, message = "title must not be empty"))]
     pub title: Option<String>,
+    #[validate(length(max = 5000, message = "body must be 5000 characters or fewer"))]
     pub body: Option<String>,
 }
 
@@ -60,85 +66,39 @@ impl From<TodoPatch> for Value {
 
 impl Patchable for TodoPatch {}
 
+#[derive(Debug, Serialize)]
+pub struct TodoPage {
+    pub todos: Vec<Object>,
+    pub total: i64,
+}
 
 pub struct TodoBMC;
 
-impl TodoBMC {
-
-    pub async fn get_all(db: Data<SurrealDBRepo>) -> Result<Vec<Object>, Error> {
-        let ast = "SELECT * FROM todo;";
+impl ModelController for TodoBMC {
+    const TABLE: &'static str = "todo";
+}
 
-        let res = db.ds.execute(ast, &db.ses, None, true).await?;
-        
-        let first_res = res.into_iter().next().expect("Did not get a response");
+impl TodoBMC {
 
-        let array: Array = W(first_res.result?).try_into()?;
+    pub async fn get_all(db: Data<SurrealDBRepo>, page: i64, page_size: i64) -> Result<TodoPage, Error> {
+        let (todos, total) = <Self as ModelController>::get_all(db, page, page_size).await?;
 
-        array.into_iter().map(|value| W(value).try_into()).collect()
+        Ok(TodoPage { todos, total })
     }
 
-    pub async fn create<T: Creatable>(db: Data<SurrealDBRepo>, tb: &str, data: T) -> Result<Object, Error> {
-        let sql = "CREATE type::table($tb) CONTENT $data RETURN *";
-
-        let data: Object = W(data.into()).try_into()?;
-
-		let vars: BTreeMap<String, Value> = map![
-			"tb".into() => tb.into(),
-			"data".into() => Value::from(data)];
-
-		let ress = db.ds.execute(sql, &db.ses, Some(vars), false).await?;
-		
-        let first_val = ress.into_iter().next().map(|r| r.result).expect("id not returned")?;
-        
-        W(first_val.first()).try_into()
+    pub async fn create<T: Creatable + Send>(db: Data<SurrealDBRepo>, data: T) -> Result<Object, Error> {
+        <Self as ModelController>::create(db, data).await
     }
 
     pub async fn get(db: Data<SurrealDBRepo>, tid: &str) -> Result<Object, Error> {
-        let sql = "SELECT * FROM $th";
-            
-            let tid = format!("todo:{}", tid);
-
-            let vars: BTreeMap<String, Value> = map!["th".into() => thing(&tid)?.into()];
-    
-            let ress = db.ds.execute(sql, &db.ses, Some(vars), true).await?;
-    
-            let first_res = ress.into_iter().next().expect("Did not get a response");
-    
-            W(first_res.result?.first()).try_into()
-           
+        <Self as ModelController>::get(db, tid).await
     }
-    
-    pub async fn update<T: Patchable>(db: Data<SurrealDBRepo>, tid: &str, data: T) -> Result<Object, Error> {
-		let sql = "UPDATE $th MERGE $data RETURN *";
-
-        let tid = format!("todo:{}", tid);
-
-		let vars = map![
-			"th".into() => thing(&tid)?.into(),
-			"data".into() => data.into()];
 
-		let ress = db.ds.execute(sql, &db.ses, Some(vars), true).await?;
-
-		let first_res = ress.into_iter().next().expect("id not returned");
-
-		let result = first_res.result?;
-        
-        W(result.first()).try_into()
-	}
+    pub async fn update<T: Patchable + Send>(db: Data<SurrealDBRepo>, tid: &str, data: T) -> Result<Object, Error> {
+        <Self as ModelController>::update(db, tid, data).await
+    }
 
     pub async fn delete(db: Data<SurrealDBRepo>, tid: &str) -> Result<String, Error> {
-		let sql = "DELETE $th RETURN *";
-
-        let tid = format!("todo:{}", tid);
-
-		let vars = map!["th".into() => thing(&tid)?.into()];
-
-		let ress = db.ds.execute(sql, &db.ses, Some(vars), false).await?;
-
-		let first_res = ress.into_iter().next().expect("id not returned");
-
-		first_res.result?;
-
-        Ok(tid)
-	}
+        <Self as ModelController>::delete(db, tid).await
+    }
 }
\ No newline at end of file
@@ -0,0 +1,113 @@
+use actix::{Actor, AtomicResponse, Context, Handler, Message, WrapFuture};
+use actix_web::web::Data;
+use surrealdb::sql::Object;
+
+use crate::prelude::*;
+use crate::model::todo_model::{Todo, TodoBMC, TodoPage, TodoPatch};
+use crate::repository::surrealdb_repo::SurrealDBRepo;
+
+/// Owns the `SurrealDBRepo` and serializes access to it behind an `Addr`: each
+/// message's future runs to completion via `ctx.wait` before the actor picks
+/// up the next one, so handlers send typed messages instead of re-opening a
+/// session per request or racing concurrent mutations against each other.
+pub struct DbActor {
+    db: Data<SurrealDBRepo>,
+}
+
+impl DbActor {
+    pub fn new(db: Data<SurrealDBRepo>) -> Self {
+        DbActor { db }
+    }
+}
+
+impl Actor for DbActor {
+    type Context = Context<Self>;
+}
+
+pub struct CreateTodo(pub Todo);
+
+impl Message for CreateTodo {
+    type Result = Result<Object, Error>;
+}
+
+impl Handler<CreateTodo> for DbActor {
+    type Result = AtomicResponse<Self, Result<Object, Error>>;
+
+    fn handle(&mut self, msg: CreateTodo, _ctx: &mut Self::Context) -> Self::Result {
+        let db = self.db.clone();
+        AtomicResponse::new(Box::pin(
+            async move { TodoBMC::create(db, msg.0).await }.into_actor(self),
+        ))
+    }
+}
+
+pub struct GetTodo(pub String);
+
+impl Message for GetTodo {
+    type Result = Result<Object, Error>;
+}
+
+impl Handler<GetTodo> for DbActor {
+    type Result = AtomicResponse<Self, Result<Object, Error>>;
+
+    fn handle(&mut self, msg: GetTodo, _ctx: &mut Self::Context) -> Self::Result {
+        let db = self.db.clone();
+        AtomicResponse::new(Box::pin(
+            async move { TodoBMC::get(db, &msg.0).await }.into_actor(self),
+        ))
+    }
+}
+
+pub struct UpdateTodo(pub String, pub TodoPatch);
+
+impl Message for UpdateTodo {
+    type Result = Result<Object, Error>;
+}
+
+impl Handler<UpdateTodo> for DbActor {
+    type Result = AtomicResponse<Self, Result<Object, Error>>;
+
+    fn handle(&mut self, msg: UpdateTodo, _ctx: &mut Self::Context) -> Self::Result {
+        let db = self.db.clone();
+        AtomicResponse::new(Box::pin(
+            async move { TodoBMC::update(db, &msg.0, msg.1).await }.into_actor(self),
+        ))
+    }
+}
+
+pub struct DeleteTodo(pub String);
+
+impl Message for DeleteTodo {
+    type Result = Result<String, Error>;
+}
+
+impl Handler<DeleteTodo> for DbActor {
+    type Result = AtomicResponse<Self, Result<String, Error>>;
+
+    fn handle(&mut self, msg: DeleteTodo, _ctx: &mut Self::Context) -> Self::Result {
+        let db = self.db.clone();
+        AtomicResponse::new(Box::pin(
+            async move { TodoBMC::delete(db, &msg.0).await }.into_actor(self),
+        ))
+    }
+}
+
+pub struct ListTodos {
+    pub page: i64,
+    pub page_size: i64,
+}
+
+impl Message for ListTodos {
+    type Result = Result<TodoPage, Error>;
+}
+
+impl Handler<ListTodos> for DbActor {
+    type Result = AtomicResponse<Self, Result<TodoPage, Error>>;
+
+    fn handle(&mut self, msg: ListTodos, _ctx: &mut Self::Context) -> Self::Result {
+        let db = self.db.clone();
+        AtomicResponse::new(Box::pin(
+            async move { TodoBMC::get_all(db, msg.page, msg.page_size).await }.into_actor(self),
+        ))
+    }
+}
@@ -0,0 +1,2 @@
+pub mod db_actor;
+pub mod surrealdb_repo;
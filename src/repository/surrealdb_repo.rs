@@ -1,6 +1,9 @@
+use std::env;
 use std::sync::Arc;
 use surrealdb::sql::Value;
-use surrealdb::{Datastore, Session, Error};
+use surrealdb::{Datastore, Session};
+
+use crate::prelude::*;
 
 pub trait Creatable: Into<Value> {}
 pub trait Patchable: Into<Value> {}
@@ -13,10 +16,20 @@ pub struct SurrealDBRepo {
 
 impl SurrealDBRepo {
     pub async fn init() -> Result<Self, Error> {
-        let ds = Arc::new(Datastore::new("file://surreal.db").await?);
-        
-        let ses = Session::for_kv().with_ns("test").with_db("test");
+        dotenv::dotenv().ok();
+
+        let path = env::var("SURREAL_PATH").unwrap_or_else(|_| "file://surreal.db".into());
+        let ns = env::var("SURREAL_NS").unwrap_or_else(|_| "test".into());
+        let db = env::var("SURREAL_DB").unwrap_or_else(|_| "test".into());
+
+        if path != "memory" && !path.starts_with("file://") && !path.starts_with("tikv://") {
+            return Err(Error::UnsupportedDatastorePath(path));
+        }
+
+        let ds = Arc::new(Datastore::new(&path).await?.with_notifications());
+
+        let ses = Session::for_kv().with_ns(&ns).with_db(&db);
 
         Ok(SurrealDBRepo { ses, ds })
     }
-}
\ No newline at end of file
+}